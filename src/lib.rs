@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 
 //! A tiny crate that provides ergonomics for printing and inspecting `Result` /
@@ -8,30 +9,157 @@
 //! encountered the value will be formatted and passed to a configurable global
 //! printer function.
 //!
-//! The default printer simply forwards to `std::println!`. Embedded users can
-//! install their own printer early in program startup using
-//! [`try_set_printer`] (or `set_printer`).
+//! `unwrap_print` diagnostics are errors, so they're kept separate from
+//! whatever an application prints on stdout: the default error printer
+//! forwards to `std::eprintln!`. [`set_error_printer`] redirects just the
+//! diagnostic path; [`try_set_printer`] (or `set_printer`) configures the
+//! general-purpose printer, which `unwrap_print` also falls back to when no
+//! error-specific printer has been installed, so existing callers of
+//! [`try_set_printer`] keep controlling where diagnostics go. Embedded users
+//! can install their own printer early in program startup using either API.
+//!
+//! The `std` feature is enabled by default and backs the global printer
+//! storage with `std::sync::Mutex`. Disabling it builds the crate as
+//! `#![no_std]` (still pulling in `alloc` for formatting), backing the same
+//! storage with `spin::Mutex` instead, so it can be used on microcontrollers.
+//! Without `std` there is no default destination to print to, so
+//! [`default_printer`] is a silent no-op until a printer is installed.
+//!
+//! Printers can be plain `fn(&str)` pointers (zero-alloc, the only option
+//! that makes sense without `alloc`-backed captures) or, via
+//! [`try_set_printer_boxed`], boxed closures that capture state such as a
+//! `log` logger handle. The optional `log` and `tracing` features build on
+//! this to ship ready-made bridges ([`install_log_printer`],
+//! [`install_tracing_printer`]) that forward diagnostics into those
+//! ecosystems' `error!` macros.
 
 extern crate alloc;
 
 use alloc::fmt::format;
+use alloc::{boxed::Box, sync::Arc};
 use core::fmt::{Arguments, Debug};
 #[cfg(feature = "track-caller")]
 use core::panic::Location;
+
+#[cfg(feature = "std")]
 use std::sync::Mutex;
 
-static PRINTER: Mutex<Option<fn(&str)>> = Mutex::new(None);
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+/// A configured printer: either a bare function pointer, or a boxed closure
+/// (stored as an `Arc` internally so it can be cloned out from behind the
+/// lock and invoked without holding it, the same way a `fn` pointer is).
+#[derive(Clone)]
+enum Printer {
+    /// A zero-alloc function pointer.
+    Fn(fn(&str)),
+    /// A closure that may capture state, e.g. a logger handle.
+    Boxed(Arc<dyn Fn(&str) + Send + Sync + 'static>),
+}
+
+impl Printer {
+    fn call(&self, s: &str) {
+        match self {
+            Printer::Fn(f) => f(s),
+            Printer::Boxed(f) => f(s),
+        }
+    }
+}
+
+/// A single configurable printer slot, guarded by a mutex. The crate keeps two
+/// of these ([`PRINTER`] and [`ERROR_PRINTER`]) so error diagnostics can be
+/// routed independently of whatever a caller uses the general sink for.
+struct PrinterSlot(Mutex<Option<Printer>>);
+
+impl PrinterSlot {
+    const fn new() -> Self {
+        PrinterSlot(Mutex::new(None))
+    }
+
+    /// Lock the slot, hiding the `std` vs `no_std` mutex API differences
+    /// (`std::sync::Mutex::lock` is fallible, `spin::Mutex::lock` is not).
+    #[cfg(feature = "std")]
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Printer>> {
+        self.0
+            .lock()
+            .unwrap()
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn lock(&self) -> spin::MutexGuard<'_, Option<Printer>> {
+        self.0.lock()
+    }
+
+    fn try_set(&self, printer: fn(&str)) -> bool {
+        let mut guard = self.lock();
+        if guard.is_some() {
+            false
+        } else {
+            *guard = Some(Printer::Fn(printer));
+            true
+        }
+    }
+
+    fn try_set_boxed(&self, printer: Box<dyn Fn(&str) + Send + Sync + 'static>) -> bool {
+        let mut guard = self.lock();
+        if guard.is_some() {
+            false
+        } else {
+            *guard = Some(Printer::Boxed(Arc::from(printer)));
+            true
+        }
+    }
+
+    fn force(&self, printer: fn(&str)) {
+        *self.lock() = Some(Printer::Fn(printer));
+    }
+
+    fn force_boxed(&self, printer: Box<dyn Fn(&str) + Send + Sync + 'static>) {
+        *self.lock() = Some(Printer::Boxed(Arc::from(printer)));
+    }
+
+    fn get(&self) -> Option<Printer> {
+        self.lock()
+            .clone()
+    }
+
+    #[cfg(test)]
+    fn reset(&self) {
+        *self.lock() = None;
+    }
+}
+
+/// General-purpose printer slot, for callers that want to route non-error
+/// output through this crate. [`print`] also falls back to this slot for the
+/// diagnostic path when [`ERROR_PRINTER`] hasn't been configured, so that
+/// pre-existing callers of [`try_set_printer`]/[`set_printer_force`] keep
+/// controlling `unwrap_print`'s destination.
+static PRINTER: PrinterSlot = PrinterSlot::new();
 
+/// The sink `unwrap_print` diagnostics are sent to when set. Takes priority
+/// over [`PRINTER`]; if neither is set, falls back to [`default_printer`]
+/// (stderr).
+static ERROR_PRINTER: PrinterSlot = PrinterSlot::new();
+
+#[cfg(feature = "std")]
 fn default_printer(s: &str) {
-    // Default behaviour: print to std output.
-    // Using `println!` directly to include a trailing newline to match most
-    // logging behavior.
-    println!(
+    // Default behaviour: write to stderr, not stdout, so diagnostics don't
+    // interleave with an application's normal output.
+    eprintln!(
         "{}",
         s
     );
 }
 
+#[cfg(not(feature = "std"))]
+fn default_printer(_s: &str) {
+    // There is no default destination without `std`. Embedded users are
+    // expected to install a printer with `try_set_printer`/`set_error_printer`
+    // during startup; until then, silently drop the message rather than
+    // panicking or requiring one be installed.
+}
+
 /// Attempt to set the global printer. This will succeed only once; subsequent
 /// calls will return `false`.
 ///
@@ -39,15 +167,15 @@ fn default_printer(s: &str) {
 /// program initialization
 /// Returns `true` if the printer was set successfully.
 pub fn try_set_printer(printer: fn(&str)) -> bool {
-    let mut guard = PRINTER
-        .lock()
-        .unwrap();
-    if guard.is_some() {
-        false
-    } else {
-        *guard = Some(printer);
-        true
-    }
+    PRINTER.try_set(printer)
+}
+
+/// Like [`try_set_printer`], but accepts a closure that may capture state
+/// (e.g. a handle to a `log` logger or `tracing` subscriber) instead of a
+/// bare function pointer. Succeeds only once; subsequent calls return
+/// `false`.
+pub fn try_set_printer_boxed(printer: Box<dyn Fn(&str) + Send + Sync + 'static>) -> bool {
+    PRINTER.try_set_boxed(printer)
 }
 
 /// Convenience wrapper which tries to set the printer and will overwrite any
@@ -56,57 +184,293 @@ pub fn try_set_printer(printer: fn(&str)) -> bool {
 #[doc(hidden)]
 pub fn set_printer_force(printer: fn(&str)) {
     // For tests and special use cases we allow replacing the global printer.
-    let mut guard = PRINTER
-        .lock()
-        .unwrap();
-    *guard = Some(printer);
+    PRINTER.force(printer);
+}
+
+/// Configure where `unwrap_print` diagnostics are sent, overwriting any
+/// previously configured error printer. Unlike [`try_set_printer`] this can be
+/// called more than once, since callers may legitimately want to change the
+/// error destination at runtime (e.g. when switching logging backends).
+/// Until one is set, diagnostics go to the general printer (see
+/// [`try_set_printer`]), or to stderr via [`default_printer`] if that isn't
+/// set either.
+pub fn set_error_printer(printer: fn(&str)) {
+    ERROR_PRINTER.force(printer);
+}
+
+/// Like [`set_error_printer`], but accepts a closure that may capture state.
+pub fn set_error_printer_boxed(printer: Box<dyn Fn(&str) + Send + Sync + 'static>) {
+    ERROR_PRINTER.force_boxed(printer);
+}
+
+#[cfg(feature = "log")]
+fn log_error_printer(s: &str) {
+    log::error!("{}", s);
+}
+
+/// Install a printer that forwards `unwrap_print` diagnostics to the `log`
+/// crate at `error!` level, so they flow into an app's existing logging
+/// pipeline instead of raw stderr.
+#[cfg(feature = "log")]
+pub fn install_log_printer() {
+    set_error_printer(log_error_printer);
+}
+
+#[cfg(feature = "tracing")]
+fn tracing_error_printer(s: &str) {
+    tracing::error!("{}", s);
+}
+
+/// Install a printer that forwards `unwrap_print` diagnostics to `tracing` at
+/// `error!` level, so they flow into an app's existing subscriber instead of
+/// raw stderr.
+#[cfg(feature = "tracing")]
+pub fn install_tracing_printer() {
+    set_error_printer(tracing_error_printer);
 }
 
 #[cfg(test)]
 #[doc(hidden)]
 pub(crate) fn reset_printer() {
-    let mut guard = PRINTER
-        .lock()
-        .unwrap();
-    *guard = None;
+    PRINTER.reset();
+}
+
+#[cfg(test)]
+#[doc(hidden)]
+pub(crate) fn reset_error_printer() {
+    ERROR_PRINTER.reset();
+}
+
+static ABORT_ON_ERROR: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+/// Toggle "strict" mode for [`PrintableResult::unwrap_print`].
+///
+/// When enabled, `unwrap_print` prints the formatted, caller-located
+/// diagnostic (same as today) and then panics, giving a single call site that
+/// behaves like `.unwrap()` with a logged message. Disabled by default, in
+/// which case `unwrap_print` degrades to a logged warning and returns the
+/// original `Result`. This does not affect [`PrintableResult::unwrap_print_or`],
+/// [`PrintableResultOrElse::unwrap_print_or_else`]/[`PrintableOptionOrElse::unwrap_print_or_else`],
+/// or [`PrintableResult::unwrap_print_expect`], which always recover a value or
+/// always panic respectively.
+pub fn set_abort_on_error(abort: bool) {
+    ABORT_ON_ERROR.store(abort, core::sync::atomic::Ordering::SeqCst);
+}
+
+fn abort_on_error() -> bool {
+    ABORT_ON_ERROR.load(core::sync::atomic::Ordering::SeqCst)
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    // A stack rather than a single slot so a nested `capture_output` call
+    // restores the enclosing capture (if any) when it's dropped, instead of
+    // clobbering it.
+    static CAPTURE_STACK: std::cell::RefCell<Vec<std::sync::Arc<std::sync::Mutex<Vec<String>>>>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// RAII guard returned by [`capture_output`].
+///
+/// While this guard is alive, output printed on the thread that created it is
+/// diverted into an in-memory buffer instead of reaching the global printer.
+/// Dropping the guard stops the diversion and restores whatever capture (or
+/// lack of one) was active before it was installed.
+#[cfg(feature = "std")]
+pub struct CaptureGuard {
+    sink: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+#[cfg(feature = "std")]
+impl CaptureGuard {
+    /// Return everything printed so far on this thread while this guard has
+    /// been the innermost active capture. Output printed while a nested
+    /// capture is active goes to that nested guard instead.
+    pub fn output(&self) -> Vec<String> {
+        self.sink
+            .lock()
+            .unwrap()
+            .clone()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for CaptureGuard {
+    fn drop(&mut self) {
+        // Remove this guard's own entry rather than blindly popping the top
+        // of the stack: guards aren't guaranteed to drop in LIFO order (e.g.
+        // an outer guard dropped explicitly before an inner one), and popping
+        // unconditionally would then discard the wrong sink.
+        CAPTURE_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack
+                .iter()
+                .rposition(|sink| std::sync::Arc::ptr_eq(sink, &self.sink))
+            {
+                stack.remove(pos);
+            }
+        });
+    }
+}
+
+/// Start capturing `unwrap_print` output on the current thread.
+///
+/// This installs a thread-local sink that takes priority over the global
+/// printer, so concurrent tests (or a multi-threaded app) can each capture
+/// their own output without clobbering the process-global printer or racing
+/// on its mutex. The returned [`CaptureGuard`] restores the previous
+/// thread-local state when dropped.
+#[cfg(feature = "std")]
+pub fn capture_output() -> CaptureGuard {
+    let sink = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    CAPTURE_STACK.with(|stack| {
+        stack
+            .borrow_mut()
+            .push(sink.clone());
+    });
+    CaptureGuard { sink }
 }
 
 /// Print an `Arguments` value using the configured printer.
 ///
 /// This helper takes care of formatting the arguments into a `String` and
-/// forwarding them to the currently installed printer (or the default
-/// printer if none is installed).
+/// forwarding them to [`ERROR_PRINTER`] if one is installed (see
+/// [`set_error_printer`]), falling back to [`PRINTER`] (see
+/// [`try_set_printer`]) and finally to [`default_printer`] (stderr) if
+/// neither is configured. If the current thread has an active
+/// [`capture_output`] guard, the message is appended to its buffer instead.
 pub fn print(args: Arguments<'_>) {
     // Format into a String - keeps the public API ergonomic and avoids forcing
     // consumers to worry about formatting internals.
     // Use `std::fmt::format` explicitly to ensure the correct function is used.
     let s = format(args);
 
-    // Acquire the printer while holding the lock briefly, then drop the lock
-    // before invoking the printer. This avoids potential deadlocks if the
-    // installed printer calls back into this crate or attempts to acquire other
+    #[cfg(feature = "std")]
+    {
+        let captured = CAPTURE_STACK.with(|stack| {
+            stack
+                .borrow()
+                .last()
+                .cloned()
+        });
+        if let Some(sink) = captured {
+            sink.lock()
+                .unwrap()
+                .push(s);
+            return;
+        }
+    }
+
+    // Read the printer while holding the lock briefly, then drop the lock
+    // before invoking it. This avoids potential deadlocks if the installed
+    // printer calls back into this crate or attempts to acquire other
     // synchronization primitives that could conflict with the mutex held here.
-    let maybe_printer = {
-        let guard = PRINTER
-            .lock()
-            .unwrap();
-        *guard
-    };
+    // ERROR_PRINTER takes priority; PRINTER is a fallback so pre-existing
+    // try_set_printer/set_printer_force callers keep working.
+    let maybe_printer = ERROR_PRINTER
+        .get()
+        .or_else(|| PRINTER.get());
 
     if let Some(printer) = maybe_printer {
-        (printer)(&s);
+        printer.call(&s);
     } else {
         default_printer(&s);
     }
 }
 
+#[cfg_attr(
+    feature = "track-caller",
+    track_caller
+)]
+fn print_result_err<E: Debug>(e: &E) {
+    #[cfg(feature = "track-caller")]
+    {
+        let caller = Location::caller();
+        print(
+            format_args!(
+                "Error at {}:{}:{}: {e:#?}",
+                caller.file(),
+                caller.line(),
+                caller.column()
+            ),
+        );
+    }
+    #[cfg(not(feature = "track-caller"))]
+    {
+        print(format_args!("Error: {e:#?}"));
+    }
+}
+
+#[cfg_attr(
+    feature = "track-caller",
+    track_caller
+)]
+fn print_option_none() {
+    #[cfg(feature = "track-caller")]
+    {
+        let caller = Location::caller();
+        print(
+            format_args!(
+                "Error at {}:{}:{}: Option::None",
+                caller.file(),
+                caller.line(),
+                caller.column()
+            ),
+        );
+    }
+    #[cfg(not(feature = "track-caller"))]
+    {
+        print(format_args!("Error: Option::None"));
+    }
+}
+
 /// Trait providing `.unwrap_print()` ergonomics.
 ///
-/// The method returns the original `Result`/`Option` as `Result`, printing a
-/// human readable message when an error/`None` is encountered.
+/// The methods print a human readable, caller-located message when an
+/// error/`None` is encountered, then either hand back the original
+/// `Result`, recover a fallback value, or panic, depending on which method is
+/// called.
 pub trait PrintableResult<T, E> {
     /// Convert into `Result<T, E>`, printing any error encountered.
+    ///
+    /// If [`set_abort_on_error(true)`](set_abort_on_error) has been called,
+    /// this panics after printing instead of returning, behaving like
+    /// `.unwrap()` with a logged message.
     fn unwrap_print(self) -> Result<T, E>;
+
+    /// Print any error/`None` encountered, then return `default` instead of
+    /// the original value.
+    fn unwrap_print_or(self, default: T) -> T;
+
+    /// Print any error/`None` encountered, then panic with `msg`, mirroring
+    /// `Option`/`Result::expect`.
+    fn unwrap_print_expect(self, msg: &str) -> T;
+}
+
+/// Companion to [`PrintableResult::unwrap_print_or`] for `Option<T>`,
+/// mirroring `Option::unwrap_or_else`: the fallback closure takes no
+/// arguments since there's no error value to hand it.
+///
+/// This is a separate trait (rather than a method on [`PrintableResult`])
+/// because `Result`'s equivalent closure takes the error value (see
+/// [`PrintableResultOrElse`]), and the two signatures can't coexist on one
+/// shared trait method.
+pub trait PrintableOptionOrElse<T> {
+    /// Print the encountered `None`, then return the result of calling `f`
+    /// instead of the original value.
+    fn unwrap_print_or_else<F: FnOnce() -> T>(self, f: F) -> T;
+}
+
+/// Companion to [`PrintableResult::unwrap_print_or`] for `Result<T, E>`,
+/// mirroring `Result::unwrap_or_else`: the fallback closure receives the
+/// error value, which is already in hand at the call site.
+///
+/// See [`PrintableOptionOrElse`] for why this isn't a method on
+/// [`PrintableResult`] itself.
+pub trait PrintableResultOrElse<T, E> {
+    /// Print the encountered error, then return the result of calling `f`
+    /// with it instead of the original value.
+    fn unwrap_print_or_else<F: FnOnce(E) -> T>(self, f: F) -> T;
 }
 
 impl<T, E: Debug> PrintableResult<T, E> for Result<T, E> {
@@ -118,26 +482,58 @@ impl<T, E: Debug> PrintableResult<T, E> for Result<T, E> {
         match self {
             Ok(v) => Ok(v),
             Err(e) => {
-                #[cfg(feature = "track-caller")]
-                {
-                    let caller = Location::caller();
-                    print(
-                        format_args!(
-                            "Error at {}:{}:{}: {e:#?}",
-                            caller.file(),
-                            caller.line(),
-                            caller.column()
-                        ),
-                    );
-                }
-                #[cfg(not(feature = "track-caller"))]
-                {
-                    print(format_args!("Error: {e:#?}"));
+                print_result_err(&e);
+                if abort_on_error() {
+                    panic!("called `unwrap_print` on an `Err` value: {e:#?}");
                 }
                 Err(e)
             }
         }
     }
+
+    #[cfg_attr(
+        feature = "track-caller",
+        track_caller
+    )]
+    fn unwrap_print_or(self, default: T) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                print_result_err(&e);
+                default
+            }
+        }
+    }
+
+    #[cfg_attr(
+        feature = "track-caller",
+        track_caller
+    )]
+    fn unwrap_print_expect(self, msg: &str) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                print_result_err(&e);
+                panic!("{msg}: {e:#?}");
+            }
+        }
+    }
+}
+
+impl<T, E: Debug> PrintableResultOrElse<T, E> for Result<T, E> {
+    #[cfg_attr(
+        feature = "track-caller",
+        track_caller
+    )]
+    fn unwrap_print_or_else<F: FnOnce(E) -> T>(self, f: F) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                print_result_err(&e);
+                f(e)
+            }
+        }
+    }
 }
 
 impl<T> PrintableResult<T, ()> for Option<T> {
@@ -149,29 +545,61 @@ impl<T> PrintableResult<T, ()> for Option<T> {
         match self {
             Some(v) => Ok(v),
             None => {
-                #[cfg(feature = "track-caller")]
-                {
-                    let caller = Location::caller();
-                    print(
-                        format_args!(
-                            "Error at {}:{}:{}: Option::None",
-                            caller.file(),
-                            caller.line(),
-                            caller.column()
-                        ),
-                    );
-                }
-                #[cfg(not(feature = "track-caller"))]
-                {
-                    print(format_args!("Error: Option::None"));
+                print_option_none();
+                if abort_on_error() {
+                    panic!("called `unwrap_print` on a `None` value");
                 }
                 Err(())
             }
         }
     }
+
+    #[cfg_attr(
+        feature = "track-caller",
+        track_caller
+    )]
+    fn unwrap_print_or(self, default: T) -> T {
+        match self {
+            Some(v) => v,
+            None => {
+                print_option_none();
+                default
+            }
+        }
+    }
+
+    #[cfg_attr(
+        feature = "track-caller",
+        track_caller
+    )]
+    fn unwrap_print_expect(self, msg: &str) -> T {
+        match self {
+            Some(v) => v,
+            None => {
+                print_option_none();
+                panic!("{msg}");
+            }
+        }
+    }
 }
 
-#[cfg(test)]
+impl<T> PrintableOptionOrElse<T> for Option<T> {
+    #[cfg_attr(
+        feature = "track-caller",
+        track_caller
+    )]
+    fn unwrap_print_or_else<F: FnOnce() -> T>(self, f: F) -> T {
+        match self {
+            Some(v) => v,
+            None => {
+                print_option_none();
+                f()
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use std::{
@@ -192,11 +620,109 @@ mod tests {
     }
 
     #[test]
-    fn set_printer_force_overwrites() {
+    fn try_set_printer_is_used_by_print_when_no_error_printer_set() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_printer();
+        reset_error_printer();
+        static CAP: StdOnceLock<StdMutex<Vec<String>>> = StdOnceLock::new();
+        CAP.set(StdMutex::new(Vec::new()))
+            .unwrap();
+        fn cap(s: &str) {
+            CAP.get()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .push(s.to_string());
+        }
+        assert!(try_set_printer(cap));
+        print(format_args!("via general printer"));
+        assert_eq!(
+            CAP.get()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .as_slice(),
+            &["via general printer"]
+        );
+        reset_printer();
+    }
+
+    #[test]
+    fn set_error_printer_takes_priority_over_general_printer() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_printer();
+        reset_error_printer();
+        fn general(_s: &str) {
+            panic!("general printer should not be invoked when an error printer is set");
+        }
+        static CAP: StdOnceLock<StdMutex<Vec<String>>> = StdOnceLock::new();
+        CAP.set(StdMutex::new(Vec::new()))
+            .unwrap();
+        fn error_printer(s: &str) {
+            CAP.get()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .push(s.to_string());
+        }
+        assert!(try_set_printer(general));
+        set_error_printer(error_printer);
+        print(format_args!("routed to error printer"));
+        assert_eq!(
+            CAP.get()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .as_slice(),
+            &["routed to error printer"]
+        );
+        reset_printer();
+        reset_error_printer();
+    }
+
+    #[test]
+    fn try_set_printer_boxed_returns_true_then_false() {
         let _guard = TEST_MUTEX
             .lock()
             .unwrap();
         reset_printer();
+        assert!(try_set_printer_boxed(Box::new(|_s: &str| {})));
+        assert!(!try_set_printer_boxed(Box::new(|_s: &str| {})));
+    }
+
+    #[test]
+    fn set_error_printer_boxed_captures_state() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        let captured = std::sync::Arc::new(StdMutex::new(Vec::new()));
+        let sink = captured.clone();
+        set_error_printer_boxed(Box::new(move |s: &str| {
+            sink.lock()
+                .unwrap()
+                .push(s.to_string());
+        }));
+        print(format_args!("closure"));
+        assert_eq!(
+            captured
+                .lock()
+                .unwrap()
+                .as_slice(),
+            &["closure"]
+        );
+    }
+
+    #[test]
+    fn set_error_printer_overwrites() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
         static FIRST: StdOnceLock<StdMutex<Vec<String>>> = StdOnceLock::new();
         FIRST
             .set(StdMutex::new(Vec::new()))
@@ -210,7 +736,7 @@ mod tests {
                 .unwrap()
                 .push(s.to_string());
         }
-        set_printer_force(first);
+        set_error_printer(first);
         print(format_args!("hello"));
         {
             let mutex = FIRST
@@ -237,7 +763,7 @@ mod tests {
                 .unwrap()
                 .push(s.to_string());
         }
-        set_printer_force(second);
+        set_error_printer(second);
         print(format_args!("world"));
         {
             let v = SECOND
@@ -255,31 +781,31 @@ mod tests {
     // The previous implementation tried to capture stdout by swapping file
     // descriptors. That approach can be fragile in certain test runners. Here
     // we replace it with a child-process based test which runs the same test
-    // executable in a subprocess and captures its stdout reliably.
+    // executable in a subprocess and captures its stderr reliably.
 
     #[test]
-    fn default_printer_writes_to_stdout() {
+    fn default_printer_writes_to_stderr() {
         let _guard = TEST_MUTEX
             .lock()
             .unwrap();
-        reset_printer();
+        reset_error_printer();
         // If we're the child process (indicated via env), do the printing and
         // exit immediately. Exiting prevents the test harness from running the
         // rest of the suite in the child process.
         if std::env::var("UNWRAP_PRINT_DEFAULT_PRINTER_CHILD").is_ok() {
             default_printer("foobar");
-            std::io::stdout()
+            std::io::stderr()
                 .flush()
                 .ok();
             std::process::exit(0);
         }
 
         // Otherwise spawn the current executable as a child with the env var set.
-        // Capture its stdout and assert the default printer produced the
+        // Capture its stderr and assert the default printer produced the
         // expected output.
         let exe = std::env::current_exe().expect("failed to find current exe");
         let out = std::process::Command::new(exe)
-            .arg("default_printer_writes_to_stdout")
+            .arg("default_printer_writes_to_stderr")
             .arg("--nocapture")
             .env(
                 "UNWRAP_PRINT_DEFAULT_PRINTER_CHILD",
@@ -287,17 +813,89 @@ mod tests {
             )
             .output()
             .expect("failed to spawn child process");
-        let stdout = String::from_utf8_lossy(&out.stdout);
+        let stderr = String::from_utf8_lossy(&out.stderr);
         assert!(
-            stdout.contains("foobar"),
-            "child stdout did not contain expected text; stdout was: {stdout:?}"
+            stderr.contains("foobar"),
+            "child stderr did not contain expected text; stderr was: {stderr:?}"
         );
     }
 
     #[test]
-    fn print_uses_installed_printer() {
-        // Ensure no custom printer is installed.
-        reset_printer();
+    fn capture_output_takes_priority_over_error_printer() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        fn noisy(_s: &str) {
+            panic!("error printer should not be invoked while capturing");
+        }
+        set_error_printer(noisy);
+
+        let capture = capture_output();
+        print(format_args!("one"));
+        print(format_args!("two"));
+        assert_eq!(
+            capture.output(),
+            vec!["one".to_string(), "two".to_string()]
+        );
+        drop(capture);
+
+        reset_error_printer();
+    }
+
+    #[test]
+    fn nested_capture_output_restores_outer_guard() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        let outer = capture_output();
+        print(format_args!("outer-before"));
+        {
+            let inner = capture_output();
+            print(format_args!("inner"));
+            assert_eq!(
+                inner.output(),
+                vec!["inner".to_string()]
+            );
+        }
+        print(format_args!("outer-after"));
+        assert_eq!(
+            outer.output(),
+            vec![
+                "outer-before".to_string(),
+                "outer-after".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn capture_output_guards_dropped_out_of_order_do_not_corrupt_each_other() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        let outer = capture_output();
+        let inner = capture_output();
+
+        // Drop the outer guard first, out of LIFO order, while the inner
+        // guard is still alive.
+        drop(outer);
+
+        print(format_args!("still-inner"));
+        assert_eq!(
+            inner.output(),
+            vec!["still-inner".to_string()]
+        );
+    }
+
+    #[test]
+    fn print_uses_installed_error_printer() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        // Ensure no custom error printer is installed.
+        reset_error_printer();
 
         // Use a OnceLock + AtomicBool to detect whether the installed printer was
         // invoked. This avoids spawning subprocesses and is deterministic across
@@ -319,7 +917,7 @@ mod tests {
             );
         }
 
-        set_printer_force(installed);
+        set_error_printer(installed);
         print(format_args!("captured"));
 
         assert!(
@@ -336,7 +934,7 @@ mod tests {
         let _guard = TEST_MUTEX
             .lock()
             .unwrap();
-        reset_printer();
+        reset_error_printer();
         static CAP_ERR: StdOnceLock<StdMutex<Vec<String>>> = StdOnceLock::new();
         CAP_ERR
             .set(StdMutex::new(Vec::new()))
@@ -349,7 +947,7 @@ mod tests {
                 .unwrap()
                 .push(s.to_string());
         }
-        set_printer_force(cap_err);
+        set_error_printer(cap_err);
         let res = Err::<(), _>(String::from("boom")).unwrap_print();
         assert!(res.is_err());
         let v = CAP_ERR
@@ -370,7 +968,7 @@ mod tests {
         let _guard = TEST_MUTEX
             .lock()
             .unwrap();
-        reset_printer();
+        reset_error_printer();
         static CAP_OPT: StdOnceLock<StdMutex<Vec<String>>> = StdOnceLock::new();
         CAP_OPT
             .set(StdMutex::new(Vec::new()))
@@ -383,7 +981,7 @@ mod tests {
                 .unwrap()
                 .push(s.to_string());
         }
-        set_printer_force(cap_opt);
+        set_error_printer(cap_opt);
         let res = Option::<i32>::None.unwrap_print();
         assert!(res.is_err());
         let v = CAP_OPT
@@ -406,7 +1004,7 @@ mod tests {
         let _guard = TEST_MUTEX
             .lock()
             .unwrap();
-        reset_printer();
+        reset_error_printer();
         static CAP_TC_RES: StdOnceLock<StdMutex<String>> = StdOnceLock::new();
         CAP_TC_RES
             .set(StdMutex::new(String::new()))
@@ -421,7 +1019,7 @@ mod tests {
             lock.clear();
             lock.push_str(s);
         }
-        set_printer_force(cap_res);
+        set_error_printer(cap_res);
         let _ = Err::<(), _>(String::from("boom")).unwrap_print();
         let s = CAP_TC_RES
             .get()
@@ -439,7 +1037,7 @@ mod tests {
         let _guard = TEST_MUTEX
             .lock()
             .unwrap();
-        reset_printer();
+        reset_error_printer();
         static CAP_TC_OPT: StdOnceLock<StdMutex<String>> = StdOnceLock::new();
         CAP_TC_OPT
             .set(StdMutex::new(String::new()))
@@ -454,7 +1052,7 @@ mod tests {
             lock.clear();
             lock.push_str(s);
         }
-        set_printer_force(cap_opt_tc);
+        set_error_printer(cap_opt_tc);
         let _ = Option::<i32>::None.unwrap_print();
         let s = CAP_TC_OPT
             .get()
@@ -465,4 +1063,104 @@ mod tests {
         assert!(s.contains("Error at "));
         assert!(s.contains("Option::None"));
     }
+
+    #[test]
+    fn unwrap_print_or_returns_default_and_prints() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        static CAP: StdOnceLock<StdMutex<Vec<String>>> = StdOnceLock::new();
+        CAP.set(StdMutex::new(Vec::new()))
+            .unwrap();
+        fn cap(s: &str) {
+            CAP.get()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .push(s.to_string());
+        }
+        set_error_printer(cap);
+        let v = Err::<i32, _>(String::from("boom")).unwrap_print_or(7);
+        assert_eq!(v, 7);
+        assert!(
+            CAP.get()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|s| s.contains("boom"))
+        );
+    }
+
+    #[test]
+    fn unwrap_print_or_else_invokes_fallback_and_prints() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        static CAP: StdOnceLock<StdMutex<Vec<String>>> = StdOnceLock::new();
+        CAP.set(StdMutex::new(Vec::new()))
+            .unwrap();
+        fn cap(s: &str) {
+            CAP.get()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .push(s.to_string());
+        }
+        set_error_printer(cap);
+        let v = Option::<i32>::None.unwrap_print_or_else(|| 42);
+        assert_eq!(v, 42);
+        assert!(
+            CAP.get()
+                .unwrap()
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|s| s.contains("Option::None"))
+        );
+    }
+
+    #[test]
+    fn unwrap_print_or_else_result_passes_error_to_closure() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        let v = Err::<i32, _>(String::from("boom")).unwrap_print_or_else(|e| e.len() as i32);
+        assert_eq!(v, 4);
+    }
+
+    #[test]
+    fn unwrap_print_expect_panics_with_message() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        set_error_printer(|_s: &str| {});
+        let result = std::panic::catch_unwind(|| {
+            Option::<i32>::None.unwrap_print_expect("missing value")
+        });
+        let payload = result.unwrap_err();
+        assert_eq!(
+            payload
+                .downcast_ref::<String>()
+                .map(String::as_str),
+            Some("missing value")
+        );
+    }
+
+    #[test]
+    fn unwrap_print_aborts_when_strict_mode_enabled() {
+        let _guard = TEST_MUTEX
+            .lock()
+            .unwrap();
+        reset_error_printer();
+        set_error_printer(|_s: &str| {});
+        set_abort_on_error(true);
+        let result = std::panic::catch_unwind(|| Err::<i32, &str>("boom").unwrap_print());
+        set_abort_on_error(false);
+        assert!(result.is_err());
+    }
 }